@@ -0,0 +1,135 @@
+use std::{collections::HashMap, env, fs, path::PathBuf};
+
+use serde::Deserialize;
+
+use crate::discovery::Browser;
+use crate::rules;
+
+/// A named browser invocation: an argv template where the literal token
+/// `{url}` is replaced with the URL being opened (and dropped entirely when
+/// no URL was given on the command line).
+///
+/// Either `command` gives the full argv explicitly, or `browser` names a
+/// browser discovered at runtime and `args` supplies the arguments to run
+/// it with (e.g. a profile directory flag and `{url}`).
+#[derive(Debug, Deserialize, Clone)]
+pub struct Launcher {
+    #[serde(default)]
+    pub command: Vec<String>,
+    pub browser: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+impl Launcher {
+    /// Builds the argv to invoke this launcher with, substituting `{url}`
+    /// with `urls`. Passing more than one URL opens them all in a single
+    /// browser invocation (e.g. as separate tabs) rather than launching the
+    /// browser once per URL.
+    pub fn argv(&self, browsers: &[Browser], urls: &[&str]) -> Vec<String> {
+        let template: Vec<String> = match &self.browser {
+            Some(browser_name) => {
+                let browser = browsers
+                    .iter()
+                    .find(|b| &b.name == browser_name)
+                    .unwrap_or_else(|| panic!("browser '{browser_name}' was not found on this system"));
+                browser.command.iter().cloned().chain(self.args.iter().cloned()).collect()
+            }
+            None => self.command.clone(),
+        };
+
+        template
+            .iter()
+            .flat_map(|arg| match arg.as_str() {
+                "{url}" => urls.iter().map(|url| url.to_string()).collect(),
+                arg => vec![arg.to_string()],
+            })
+            .collect()
+    }
+}
+
+/// A single routing rule: if the URL's host matches `domains`, `launcher` is
+/// used to open it. Entries in `domains` support `*.example.com` wildcards,
+/// `=example.com` exact-only matches, and a leading `!` to exclude a more
+/// specific domain from an otherwise matching rule (see [`rules::matches`]).
+/// `resolve_redirects` overrides the top-level setting of the same name for
+/// this rule specifically.
+#[derive(Debug, Deserialize)]
+pub struct Rule {
+    pub domains: Vec<String>,
+    pub launcher: String,
+    #[serde(default)]
+    pub resolve_redirects: Option<bool>,
+}
+
+fn default_redirect_timeout_ms() -> u64 {
+    3000
+}
+
+fn default_max_redirects() -> u32 {
+    5
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Config {
+    pub default_launcher: String,
+    pub launchers: HashMap<String, Launcher>,
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+    /// Follow redirects and match on the final destination host rather than
+    /// the URL as given, to see through link shorteners and SSO
+    /// redirectors. Off by default; a matching rule's own setting wins.
+    #[serde(default)]
+    pub resolve_redirects: bool,
+    #[serde(default = "default_redirect_timeout_ms")]
+    pub redirect_timeout_ms: u64,
+    #[serde(default = "default_max_redirects")]
+    pub max_redirects: u32,
+}
+
+impl Config {
+    /// Finds the first rule whose domains match `host`.
+    pub fn matching_rule(&self, host: &str) -> Option<&Rule> {
+        self.rules.iter().find(|rule| rules::matches(host, &rule.domains))
+    }
+
+    /// Whether redirects should be resolved before routing, for a request
+    /// that currently matched `rule` (if any).
+    pub fn should_resolve_redirects(&self, rule: Option<&Rule>) -> bool {
+        rule.and_then(|rule| rule.resolve_redirects).unwrap_or(self.resolve_redirects)
+    }
+
+    /// The name of the launcher a host routes to, walking `rules` in order
+    /// and falling back to `default_launcher` if none match.
+    pub fn launcher_name_for_host(&self, host: Option<&str>) -> &str {
+        host.and_then(|host| self.matching_rule(host))
+            .map(|rule| rule.launcher.as_str())
+            .unwrap_or(&self.default_launcher)
+    }
+
+    pub fn launcher(&self, name: &str) -> &Launcher {
+        self.launchers
+            .get(name)
+            .unwrap_or_else(|| panic!("launcher '{name}' is not defined in config"))
+    }
+
+    /// Looks up the launcher for a host, walking `rules` in order and
+    /// falling back to `default_launcher` if none match.
+    pub fn launcher_for_host(&self, host: Option<&str>) -> &Launcher {
+        self.launcher(self.launcher_name_for_host(host))
+    }
+}
+
+fn config_path() -> PathBuf {
+    let config_home = env::var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(env::var("HOME").expect("HOME not set")).join(".config"));
+    config_home.join("choosr").join("config.toml")
+}
+
+pub fn load_config() -> Config {
+    let path = config_path();
+    let contents = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("could not read config file {}: {e}", path.display()));
+    toml::from_str(&contents).expect("could not parse config file")
+}
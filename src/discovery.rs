@@ -0,0 +1,117 @@
+use std::path::Path;
+
+/// A browser executable found on the current system. `name` is a stable,
+/// cross-platform identifier (`chrome`, `firefox`, `chromium`, ...) so a
+/// config launcher can reference a browser by name regardless of platform
+/// or of how it ended up installed (PATH binary vs. flatpak vs. app bundle).
+#[derive(Debug, Clone)]
+pub struct Browser {
+    pub name: String,
+    pub command: Vec<String>,
+}
+
+/// Stable cross-platform browser names, each paired with the PATH binaries
+/// and flatpak app ID that can provide it on Linux. Keeping these names the
+/// same across platforms is what lets a config say `browser = "chrome"` and
+/// have it resolve regardless of how that browser ended up installed.
+#[cfg(target_os = "linux")]
+const KNOWN_BROWSERS: &[(&str, &[&str], &str)] = &[
+    ("chrome", &["google-chrome-stable", "google-chrome"], "com.google.Chrome"),
+    ("chromium", &["chromium", "chromium-browser"], "org.chromium.Chromium"),
+    ("firefox", &["firefox"], "org.mozilla.firefox"),
+];
+
+#[cfg(target_os = "linux")]
+pub fn discover_browsers() -> Vec<Browser> {
+    KNOWN_BROWSERS
+        .iter()
+        .filter_map(|(name, bins, app_id)| {
+            if let Some(path) = bins.iter().find_map(|bin| find_on_path(bin)) {
+                return Some(Browser {
+                    name: name.to_string(),
+                    command: vec![path],
+                });
+            }
+            if flatpak_app_installed(app_id) {
+                return Some(Browser {
+                    name: name.to_string(),
+                    command: vec![
+                        "/usr/bin/flatpak".to_string(),
+                        "run".to_string(),
+                        app_id.to_string(),
+                    ],
+                });
+            }
+            None
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn find_on_path(bin: &str) -> Option<String> {
+    let path_var = std::env::var_os("PATH")?;
+    std::env::split_paths(&path_var)
+        .map(|dir| dir.join(bin))
+        .find(|candidate| candidate.is_file())
+        .map(|path| path.to_string_lossy().into_owned())
+}
+
+#[cfg(target_os = "linux")]
+fn flatpak_app_installed(app_id: &str) -> bool {
+    let home = std::env::var("HOME").unwrap_or_default();
+    [
+        "/var/lib/flatpak/app".to_string(),
+        format!("{home}/.local/share/flatpak/app"),
+    ]
+    .iter()
+    .any(|base| Path::new(base).join(app_id).is_dir())
+}
+
+#[cfg(target_os = "macos")]
+const KNOWN_APP_BUNDLES: &[(&str, &str)] = &[
+    ("chrome", "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome"),
+    ("firefox", "/Applications/Firefox.app/Contents/MacOS/firefox"),
+    ("safari", "/Applications/Safari.app/Contents/MacOS/Safari"),
+];
+
+#[cfg(target_os = "macos")]
+pub fn discover_browsers() -> Vec<Browser> {
+    KNOWN_APP_BUNDLES
+        .iter()
+        .filter(|(_, path)| Path::new(path).is_file())
+        .map(|(name, path)| Browser {
+            name: name.to_string(),
+            command: vec![path.to_string()],
+        })
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+const KNOWN_APP_PATH_EXES: &[(&str, &str)] = &[
+    ("chrome", "chrome.exe"),
+    ("firefox", "firefox.exe"),
+    ("edge", "msedge.exe"),
+];
+
+#[cfg(target_os = "windows")]
+pub fn discover_browsers() -> Vec<Browser> {
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let Ok(app_paths) = hklm.open_subkey("SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\App Paths") else {
+        return Vec::new();
+    };
+
+    KNOWN_APP_PATH_EXES
+        .iter()
+        .filter_map(|(name, exe)| {
+            let key = app_paths.open_subkey(exe).ok()?;
+            let path: String = key.get_value("").ok()?;
+            Some(Browser {
+                name: name.to_string(),
+                command: vec![path],
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,9 @@
+use url::Url;
+
+/// Extracts a lowercased, trailing-dot-stripped host from a URL string.
+/// Returns `None` for unparseable URLs or schemes without a host (e.g. `file:`, `about:`).
+pub fn extract_host(url_str: &str) -> Option<String> {
+    let parsed = Url::parse(url_str).ok()?;
+    let host = parsed.host_str()?.to_lowercase();
+    Some(host.strip_suffix('.').map(str::to_string).unwrap_or(host))
+}
@@ -1,41 +1,107 @@
-use std::{
-    env,
-    fs::File,
-    io::{prelude::*, BufReader},
-    path::Path,
-};
-use subprocess::{Popen,PopenConfig};
-
-fn launch_browser(profile_dir: &str, url: Option<String>) {
-    let profile_dir_arg = &format!("--profile-directory={}", profile_dir);
-    let mut command: Vec<&str> = vec!["/usr/bin/flatpak","run","--branch=stable","--arch=x86_64","--command=/app/bin/chrome","--file-forwarding","com.google.Chrome", profile_dir_arg];
-
-    let unwrapped_url;
-    if url.is_some() {
-        unwrapped_url =url.unwrap();
-        command.push(&unwrapped_url);
-    }
+mod config;
+mod discovery;
+mod host;
+mod resolve;
+mod rules;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use clap::Parser;
+use subprocess::{Popen, PopenConfig};
+
+use config::{load_config, Config, Launcher};
+use discovery::Browser;
+
+/// Opens one or more URLs in the browser profile configured for their domain.
+#[derive(Parser)]
+#[command(author, version, about)]
+struct Cli {
+    /// URLs to open, each routed independently
+    urls: Vec<String>,
 
-    Popen::create(&command, PopenConfig::default()).expect("Flatpak should be runnable").wait().expect("Chrome should launch cleanly");
+    /// Read additional URLs to open from a file, one per line
+    #[arg(short, long)]
+    file: Option<PathBuf>,
 }
 
-fn load_domains(filename: impl AsRef<Path>) -> Vec<String> {
-    let file = File::open(filename).expect("Domain file not found");
-    let buf = BufReader::new(file);
-    buf.lines().map(|l| l.expect("Could not parse line")).collect()
+/// Spawns the browser without waiting for it to exit, so launching several
+/// launcher groups in a batch doesn't block the next one on the first
+/// browser window closing.
+fn spawn_browser(launcher: &Launcher, browsers: &[Browser], urls: &[&str]) -> Popen {
+    let argv = launcher.argv(browsers, urls);
+    let command: Vec<&str> = argv.iter().map(String::as_str).collect();
+
+    Popen::create(&command, PopenConfig::default()).expect("Browser should be runnable")
 }
 
+/// Resolves the host that should be used to route `url`, following
+/// redirects first if the matched rule (or the global config) opts into it.
+fn routing_host(url: &str, config: &Config) -> Option<String> {
+    let host = host::extract_host(url);
+    let rule = host.as_deref().and_then(|host| config.matching_rule(host));
 
+    if config.should_resolve_redirects(rule) {
+        resolve::resolve_final_host(
+            url,
+            Duration::from_millis(config.redirect_timeout_ms),
+            config.max_redirects,
+        )
+        .or(host)
+    } else {
+        host
+    }
+}
+
+fn read_urls_file(path: &Path) -> Vec<String> {
+    fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("could not read URL file {}: {e}", path.display()))
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    let url = match args.len() {
-        1 => None,
-        2 => Some(&args[1]),
-        _ => panic!("bad number of args")
-    };
-    let domains = load_domains("work.txt");
-    println!("{domains:?}");
-    let profile_dir = if (url.is_some() & domains.contains(url.unwrap())) {"Profile 2"} else {"Default"};
-    launch_browser(profile_dir, url.cloned())
+    let cli = Cli::parse();
+    let mut urls = cli.urls;
+    if let Some(file) = &cli.file {
+        urls.extend(read_urls_file(file));
+    }
+
+    let config = load_config();
+    let browsers = discovery::discover_browsers();
+
+    if urls.is_empty() {
+        let launcher = config.launcher_for_host(None);
+        spawn_browser(launcher, &browsers, &[])
+            .wait()
+            .expect("Browser should launch cleanly");
+        return;
+    }
+
+    // Group URLs by the launcher they route to, preserving first-seen order,
+    // so URLs sharing a launcher open in a single browser invocation.
+    let mut groups: Vec<(&str, Vec<&str>)> = Vec::new();
+    for url in &urls {
+        let host = routing_host(url, &config);
+        let launcher_name = config.launcher_name_for_host(host.as_deref());
+        match groups.iter_mut().find(|(name, _)| *name == launcher_name) {
+            Some((_, urls)) => urls.push(url),
+            None => groups.push((launcher_name, vec![url])),
+        }
+    }
+
+    // Spawn every group's browser before waiting on any of them, so a slow
+    // cold start for one launcher doesn't delay the others.
+    let mut processes: Vec<Popen> = Vec::new();
+    for (launcher_name, urls) in groups {
+        let launcher = config.launcher(launcher_name);
+        processes.push(spawn_browser(launcher, &browsers, &urls));
+    }
+    for mut process in processes {
+        process.wait().expect("Browser should launch cleanly");
+    }
 }
@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+use crate::host::extract_host;
+
+/// Follows redirects from `url` and returns the host of the final
+/// destination, to see through link shorteners and SSO redirectors that
+/// would otherwise defeat host-based routing. Returns `None` on any network
+/// failure or timeout so callers can fall back to the original host rather
+/// than block indefinitely.
+pub fn resolve_final_host(url: &str, timeout: Duration, max_redirects: u32) -> Option<String> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(timeout)
+        .redirects(max_redirects)
+        .build();
+
+    let response = agent.head(url).call().or_else(|_| agent.get(url).call()).ok()?;
+    extract_host(response.get_url())
+}
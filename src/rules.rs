@@ -0,0 +1,133 @@
+/// How a pattern's domain labels must line up against a host's labels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MatchKind {
+    /// Bare domain (`example.com`): matches the domain itself and any subdomain.
+    Suffix,
+    /// `*.example.com`: matches subdomains only, not the bare domain.
+    Wildcard,
+    /// `=example.com`: matches the domain itself only, not subdomains.
+    Exact,
+}
+
+/// A single domain pattern within a rule's domain list.
+///
+/// Supports:
+/// - bare domains (`example.com`): match the domain itself and any subdomain
+/// - wildcard domains (`*.example.com`): match subdomains only, not the bare domain
+/// - exact domains (`=example.com`): match the domain itself only, not subdomains
+/// - negation (a leading `!`): exclude rather than include a match, so a rule
+///   can say "corp.example.com, except public.corp.example.com"
+#[derive(Debug, Clone)]
+struct Pattern {
+    negate: bool,
+    kind: MatchKind,
+    labels: Vec<String>,
+}
+
+impl Pattern {
+    fn parse(raw: &str) -> Pattern {
+        let (negate, rest) = match raw.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, raw),
+        };
+        let (kind, rest) = match rest.strip_prefix("*.") {
+            Some(rest) => (MatchKind::Wildcard, rest),
+            None => match rest.strip_prefix('=') {
+                Some(rest) => (MatchKind::Exact, rest),
+                None => (MatchKind::Suffix, rest),
+            },
+        };
+        Pattern {
+            negate,
+            kind,
+            labels: rest.split('.').map(str::to_lowercase).collect(),
+        }
+    }
+
+    /// If `host_labels` matches this pattern, returns its specificity (the
+    /// number of labels matched) so the most specific of several matching
+    /// patterns can win.
+    fn specificity(&self, host_labels: &[&str]) -> Option<usize> {
+        if self.labels.len() > host_labels.len() {
+            return None;
+        }
+        let suffix = &host_labels[host_labels.len() - self.labels.len()..];
+        let pattern_labels: Vec<&str> = self.labels.iter().map(String::as_str).collect();
+        if suffix != pattern_labels.as_slice() {
+            return None;
+        }
+        let is_bare_domain = suffix.len() == host_labels.len();
+        match self.kind {
+            MatchKind::Suffix => Some(self.labels.len()),
+            MatchKind::Wildcard if is_bare_domain => None,
+            MatchKind::Exact if !is_bare_domain => None,
+            MatchKind::Wildcard | MatchKind::Exact => Some(self.labels.len()),
+        }
+    }
+}
+
+/// Does `host` match this list of domain patterns? The most specific
+/// matching pattern wins (ties broken by the last one listed), and a
+/// negated winning pattern means the list does not match.
+pub fn matches(host: &str, patterns: &[String]) -> bool {
+    let host_labels: Vec<&str> = host.split('.').collect();
+    patterns
+        .iter()
+        .map(|raw| Pattern::parse(raw))
+        .filter_map(|pattern| pattern.specificity(&host_labels).map(|spec| (spec, pattern.negate)))
+        .enumerate()
+        .max_by_key(|(index, (spec, _))| (*spec, *index))
+        .map(|(_, (_, negate))| !negate)
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn bare_domain_matches_itself_and_subdomains() {
+        let domains = patterns(&["example.com"]);
+        assert!(matches("example.com", &domains));
+        assert!(matches("mail.example.com", &domains));
+        assert!(!matches("notexample.com", &domains));
+    }
+
+    #[test]
+    fn wildcard_domain_matches_subdomains_only() {
+        let domains = patterns(&["*.example.com"]);
+        assert!(matches("mail.example.com", &domains));
+        assert!(!matches("example.com", &domains));
+    }
+
+    #[test]
+    fn negated_pattern_excludes_a_more_specific_subdomain() {
+        let domains = patterns(&["corp.example.com", "!public.corp.example.com"]);
+        assert!(matches("corp.example.com", &domains));
+        assert!(matches("intranet.corp.example.com", &domains));
+        assert!(!matches("public.corp.example.com", &domains));
+    }
+
+    #[test]
+    fn exact_domain_matches_only_itself() {
+        let domains = patterns(&["=example.com"]);
+        assert!(matches("example.com", &domains));
+        assert!(!matches("mail.example.com", &domains));
+    }
+
+    #[test]
+    fn no_matching_pattern_is_not_a_match() {
+        let domains = patterns(&["example.com"]);
+        assert!(!matches("example.org", &domains));
+    }
+
+    #[test]
+    fn pattern_matching_is_case_insensitive() {
+        let domains = patterns(&["Corp.Example.com"]);
+        assert!(matches("corp.example.com", &domains));
+    }
+}